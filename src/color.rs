@@ -0,0 +1,18 @@
+// A simple RGB colour, stored as normalized floats (0.0-1.0 per channel) since that's what
+// goes straight into the vertex buffers the consoles upload to the GPU.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RGB {
+    pub r : f32,
+    pub g : f32,
+    pub b : f32
+}
+
+impl RGB {
+    pub fn new(r : f32, g : f32, b : f32) -> RGB {
+        RGB{ r, g, b }
+    }
+
+    pub fn from_u8(r : u8, g : u8, b : u8) -> RGB {
+        RGB{ r: r as f32 / 255.0, g: g as f32 / 255.0, b: b as f32 / 255.0 }
+    }
+}
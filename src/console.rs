@@ -0,0 +1,433 @@
+extern crate gl;
+use std::collections::HashSet;
+use super::font::Font;
+use super::shader::Shader;
+use super::color::RGB;
+use super::glyph_cache::GlyphCache;
+
+// Shared behaviour for anything that can sit in `Rltk::consoles` and be drawn each frame.
+pub trait Console {
+    fn at(&self, x : i32, y : i32) -> usize;
+    fn cls(&mut self);
+    fn cls_bg(&mut self, background : RGB);
+    fn print(&mut self, x : i32, y : i32, output : &str);
+    fn print_color(&mut self, x : i32, y : i32, fg : RGB, bg : RGB, output : &str);
+
+    // Patches whatever's changed since the last call into the GPU-side buffers.
+    fn rebuild_if_dirty(&mut self);
+    fn gl_draw(&mut self, font : &Font, shader : &Shader);
+
+    // Pixel-space bounding box of whatever was patched by the most recent `rebuild_if_dirty`
+    // call, for scissoring the draw/clear to just that region. `None` means "treat this as a
+    // full redraw" (no rebuild has run yet, or the last rebuild was a full one); a rebuild
+    // with nothing dirty reports `Some((0, 0, 0, 0))`.
+    fn get_damage_bounds(&self) -> Option<(i32, i32, i32, i32)>;
+
+    // Re-derives column/row count from new pixel dimensions and a tile size, reallocating
+    // the backing tile buffer and forcing a full rebuild.
+    fn resize_pixels(&mut self, width_pixels : u32, height_pixels : u32);
+
+    // Draws this console's proportional text layer, if it has one, by looking glyphs up in
+    // the given cache instead of assuming a uniform grid. No-op by default, since only a
+    // console backed by a `GlyphCache` (e.g. `TextConsole`) needs it.
+    fn gl_draw_proportional(&mut self, _glyph_cache : &mut GlyphCache, _shader : &Shader) { }
+}
+
+#[derive(Clone, Copy)]
+struct Tile {
+    glyph : u8,
+    fg : RGB,
+    bg : RGB
+}
+
+impl Default for Tile {
+    fn default() -> Tile {
+        Tile{ glyph: 0, fg: RGB::new(1.0, 1.0, 1.0), bg: RGB::new(0.0, 0.0, 0.0) }
+    }
+}
+
+// Re-derives column/row count from new pixel dimensions and a tile size, kept free of `self`
+// so the "at least one column/row, even if it doesn't divide evenly" clamping can be tested
+// without a live GL context.
+fn cols_rows_for_pixels(width_pixels : u32, height_pixels : u32, tile_size : (u32, u32)) -> (u32, u32) {
+    ((width_pixels / tile_size.0).max(1), (height_pixels / tile_size.1).max(1))
+}
+
+// A fixed-grid console backed by a single VBO, one quad per cell. Tracks which cells have
+// changed since the last rebuild so `rebuild_if_dirty` only has to patch those vertices
+// instead of re-uploading the whole mesh.
+pub struct SimpleConsole {
+    width : u32,
+    height : u32,
+    tile_size : (u32, u32),
+    tiles : Vec<Tile>,
+
+    dirty_cells : HashSet<usize>,
+    dirty_bounds : Option<(i32, i32, i32, i32)>,
+    force_full_rebuild : bool,
+    last_damage : Option<(i32, i32, i32, i32)>,
+
+    vao : gl::types::GLuint,
+    vbo : gl::types::GLuint,
+    floats_per_tile : usize
+}
+
+impl SimpleConsole {
+    pub fn new(width : u32, height : u32, tile_size : (u32, u32)) -> SimpleConsole {
+        let floats_per_tile = 6 * 10; // 6 verts/quad, 10 floats/vert (pos, uv, fg, bg)
+        let tile_count = (width * height) as usize;
+
+        let (mut vao, mut vbo) = (0, 0);
+        unsafe {
+            gl::GenVertexArrays(1, &mut vao);
+            gl::GenBuffers(1, &mut vbo);
+            gl::BindVertexArray(vao);
+            gl::BindBuffer(gl::ARRAY_BUFFER, vbo);
+            gl::BufferData(gl::ARRAY_BUFFER, (tile_count * floats_per_tile * std::mem::size_of::<f32>()) as isize,
+                std::ptr::null(), gl::DYNAMIC_DRAW);
+
+            // Bind the pos(2)/uv(2)/fg(3)/bg(3) layout `patch_cell` writes to this VAO, so the
+            // shader actually receives vertex data instead of reading an unconfigured stream.
+            let stride = 10 * std::mem::size_of::<f32>() as i32;
+            gl::VertexAttribPointer(0, 2, gl::FLOAT, gl::FALSE, stride, std::ptr::null());
+            gl::EnableVertexAttribArray(0);
+            gl::VertexAttribPointer(1, 2, gl::FLOAT, gl::FALSE, stride, (2 * std::mem::size_of::<f32>()) as *const _);
+            gl::EnableVertexAttribArray(1);
+            gl::VertexAttribPointer(2, 3, gl::FLOAT, gl::FALSE, stride, (4 * std::mem::size_of::<f32>()) as *const _);
+            gl::EnableVertexAttribArray(2);
+            gl::VertexAttribPointer(3, 3, gl::FLOAT, gl::FALSE, stride, (7 * std::mem::size_of::<f32>()) as *const _);
+            gl::EnableVertexAttribArray(3);
+        }
+
+        let mut console = SimpleConsole {
+            width, height, tile_size,
+            tiles: vec![Tile::default(); tile_count],
+            dirty_cells: HashSet::new(),
+            dirty_bounds: None,
+            force_full_rebuild: true,
+            last_damage: None,
+            vao, vbo,
+            floats_per_tile
+        };
+        console.mark_all_dirty();
+        console
+    }
+
+    fn idx(&self, x : i32, y : i32) -> usize {
+        (y as u32 * self.width + x as u32) as usize
+    }
+
+    fn mark_dirty(&mut self, x : i32, y : i32) {
+        let idx = self.idx(x, y);
+        self.dirty_cells.insert(idx);
+        self.dirty_bounds = Some(match self.dirty_bounds {
+            None => (x, y, 1, 1),
+            Some((bx, by, bw, bh)) => {
+                let x0 = bx.min(x);
+                let y0 = by.min(y);
+                let x1 = (bx + bw).max(x + 1);
+                let y1 = (by + bh).max(y + 1);
+                (x0, y0, x1 - x0, y1 - y0)
+            }
+        });
+    }
+
+    fn mark_all_dirty(&mut self) {
+        self.force_full_rebuild = true;
+    }
+
+    // Rewrites the 6-vertex quad for a single cell directly into the VBO via glBufferSubData,
+    // at the byte offset that cell owns.
+    fn patch_cell(&self, idx : usize) {
+        let tile = self.tiles[idx];
+        let x = (idx as u32 % self.width) as f32;
+        let y = (idx as u32 / self.width) as f32;
+        let (tw, th) = (self.tile_size.0 as f32, self.tile_size.1 as f32);
+
+        // Two triangles covering this cell's pixel rect: pos(2), uv(2), fg(3), bg(3) per
+        // vertex so `gl_draw` doesn't need a separate background pass.
+        let (fr, fg_, fb) = (tile.fg.r, tile.fg.g, tile.fg.b);
+        let (br, bg_, bb) = (tile.bg.r, tile.bg.g, tile.bg.b);
+        let quad : [f32; 60] = [
+            x*tw,      y*th,      0.0, 0.0, fr, fg_, fb, br, bg_, bb,
+            x*tw,      (y+1.0)*th,0.0, 1.0, fr, fg_, fb, br, bg_, bb,
+            (x+1.0)*tw,(y+1.0)*th,1.0, 1.0, fr, fg_, fb, br, bg_, bb,
+            x*tw,      y*th,      0.0, 0.0, fr, fg_, fb, br, bg_, bb,
+            (x+1.0)*tw,(y+1.0)*th,1.0, 1.0, fr, fg_, fb, br, bg_, bb,
+            (x+1.0)*tw,y*th,      1.0, 0.0, fr, fg_, fb, br, bg_, bb
+        ];
+
+        let offset = (idx * self.floats_per_tile * std::mem::size_of::<f32>()) as isize;
+        unsafe {
+            gl::BindBuffer(gl::ARRAY_BUFFER, self.vbo);
+            gl::BufferSubData(gl::ARRAY_BUFFER, offset, (quad.len() * std::mem::size_of::<f32>()) as isize,
+                quad.as_ptr() as *const _);
+        }
+    }
+
+    fn rebuild_all(&mut self) {
+        for idx in 0..self.tiles.len() {
+            self.patch_cell(idx);
+        }
+    }
+}
+
+impl Console for SimpleConsole {
+    fn at(&self, x : i32, y : i32) -> usize {
+        self.idx(x, y)
+    }
+
+    fn cls(&mut self) {
+        for tile in self.tiles.iter_mut() {
+            *tile = Tile::default();
+        }
+        self.mark_all_dirty();
+    }
+
+    fn cls_bg(&mut self, background : RGB) {
+        for tile in self.tiles.iter_mut() {
+            tile.bg = background;
+        }
+        self.mark_all_dirty();
+    }
+
+    fn print(&mut self, x : i32, y : i32, output : &str) {
+        for (i, c) in output.chars().enumerate() {
+            let cx = x + i as i32;
+            if cx < 0 || cx as u32 >= self.width || y < 0 || y as u32 >= self.height { continue; }
+            let idx = self.idx(cx, y);
+            self.tiles[idx].glyph = c as u8;
+            self.mark_dirty(cx, y);
+        }
+    }
+
+    fn print_color(&mut self, x : i32, y : i32, fg : RGB, bg : RGB, output : &str) {
+        for (i, c) in output.chars().enumerate() {
+            let cx = x + i as i32;
+            if cx < 0 || cx as u32 >= self.width || y < 0 || y as u32 >= self.height { continue; }
+            let idx = self.idx(cx, y);
+            self.tiles[idx] = Tile{ glyph: c as u8, fg, bg };
+            self.mark_dirty(cx, y);
+        }
+    }
+
+    fn rebuild_if_dirty(&mut self) {
+        if self.force_full_rebuild {
+            self.rebuild_all();
+            self.last_damage = None;
+            self.force_full_rebuild = false;
+            self.dirty_cells.clear();
+            self.dirty_bounds = None;
+            return;
+        }
+
+        if self.dirty_cells.is_empty() {
+            self.last_damage = Some((0, 0, 0, 0));
+            return;
+        }
+
+        let dirty : Vec<usize> = self.dirty_cells.drain().collect();
+        for idx in dirty {
+            self.patch_cell(idx);
+        }
+
+        let (tw, th) = (self.tile_size.0 as i32, self.tile_size.1 as i32);
+        self.last_damage = self.dirty_bounds.map(|(x, y, w, h)| (x * tw, y * th, w * tw, h * th));
+        self.dirty_bounds = None;
+    }
+
+    fn gl_draw(&mut self, font : &Font, shader : &Shader) {
+        shader.useProgram();
+        font.bind_texture();
+        unsafe {
+            gl::BindVertexArray(self.vao);
+            gl::DrawArrays(gl::TRIANGLES, 0, (self.tiles.len() * 6) as i32);
+        }
+    }
+
+    fn get_damage_bounds(&self) -> Option<(i32, i32, i32, i32)> {
+        self.last_damage
+    }
+
+    fn resize_pixels(&mut self, width_pixels : u32, height_pixels : u32) {
+        let (cols, rows) = cols_rows_for_pixels(width_pixels, height_pixels, self.tile_size);
+        self.width = cols;
+        self.height = rows;
+        self.tiles = vec![Tile::default(); (cols * rows) as usize];
+
+        unsafe {
+            gl::BindBuffer(gl::ARRAY_BUFFER, self.vbo);
+            gl::BufferData(gl::ARRAY_BUFFER, (self.tiles.len() * self.floats_per_tile * std::mem::size_of::<f32>()) as isize,
+                std::ptr::null(), gl::DYNAMIC_DRAW);
+        }
+
+        self.mark_all_dirty();
+    }
+}
+
+// A console that renders scalable UI text through a `GlyphCache` instead of the fixed CP437
+// grid `SimpleConsole` uses. Proportional glyph widths don't fit the cell-indexed damage
+// tracking above, so this rebuilds its (typically small) vertex list from scratch on every
+// draw rather than patching individual cells.
+pub struct TextConsole {
+    width_pixels : u32,
+    height_pixels : u32,
+    px_size : u32,
+    runs : Vec<(i32, i32, String, RGB)>,
+
+    dirty_bounds : Option<(i32, i32, i32, i32)>,
+    force_full_rebuild : bool,
+    last_damage : Option<(i32, i32, i32, i32)>,
+
+    vao : gl::types::GLuint,
+    vbo : gl::types::GLuint
+}
+
+impl TextConsole {
+    pub fn new(width_pixels : u32, height_pixels : u32, px_size : u32) -> TextConsole {
+        let (mut vao, mut vbo) = (0, 0);
+        unsafe {
+            gl::GenVertexArrays(1, &mut vao);
+            gl::GenBuffers(1, &mut vbo);
+            gl::BindVertexArray(vao);
+            gl::BindBuffer(gl::ARRAY_BUFFER, vbo);
+
+            // Bind the pos(2)/uv(2)/fg(3) layout `gl_draw_proportional` rebuilds every frame -
+            // there's no bg component, since proportional text is drawn over whatever its
+            // console already holds rather than painting its own background.
+            let stride = 7 * std::mem::size_of::<f32>() as i32;
+            gl::VertexAttribPointer(0, 2, gl::FLOAT, gl::FALSE, stride, std::ptr::null());
+            gl::EnableVertexAttribArray(0);
+            gl::VertexAttribPointer(1, 2, gl::FLOAT, gl::FALSE, stride, (2 * std::mem::size_of::<f32>()) as *const _);
+            gl::EnableVertexAttribArray(1);
+            gl::VertexAttribPointer(2, 3, gl::FLOAT, gl::FALSE, stride, (4 * std::mem::size_of::<f32>()) as *const _);
+            gl::EnableVertexAttribArray(2);
+        }
+        TextConsole {
+            width_pixels, height_pixels, px_size, runs: Vec::new(),
+            dirty_bounds: None, force_full_rebuild: true, last_damage: None,
+            vao, vbo
+        }
+    }
+
+    // Unions a run's approximate pixel footprint (chars * px_size wide, one px_size tall -
+    // proportional glyphs vary, but this is close enough to size a scissor rect) into the
+    // damage bounds `rebuild_if_dirty` will report next.
+    fn mark_dirty(&mut self, x : i32, y : i32, output : &str) {
+        let (w, h) = (output.chars().count() as i32 * self.px_size as i32, self.px_size as i32);
+        self.dirty_bounds = Some(match self.dirty_bounds {
+            None => (x, y, w, h),
+            Some((bx, by, bw, bh)) => {
+                let x0 = bx.min(x);
+                let y0 = by.min(y);
+                let x1 = (bx + bw).max(x + w);
+                let y1 = (by + bh).max(y + h);
+                (x0, y0, x1 - x0, y1 - y0)
+            }
+        });
+    }
+}
+
+impl Console for TextConsole {
+    fn at(&self, _x : i32, _y : i32) -> usize { 0 }
+
+    fn cls(&mut self) {
+        self.runs.clear();
+        self.force_full_rebuild = true;
+    }
+
+    fn cls_bg(&mut self, _background : RGB) { }
+
+    fn print(&mut self, x : i32, y : i32, output : &str) {
+        self.mark_dirty(x, y, output);
+        self.runs.push((x, y, output.to_string(), RGB::new(1.0, 1.0, 1.0)));
+    }
+
+    fn print_color(&mut self, x : i32, y : i32, fg : RGB, _bg : RGB, output : &str) {
+        self.mark_dirty(x, y, output);
+        self.runs.push((x, y, output.to_string(), fg));
+    }
+
+    // There's no fixed-grid VBO to patch; proportional text is rebuilt wholesale in
+    // `gl_draw_proportional` each frame. This just settles what `get_damage_bounds` reports
+    // for this frame, mirroring `SimpleConsole::rebuild_if_dirty`'s three cases.
+    fn rebuild_if_dirty(&mut self) {
+        if self.force_full_rebuild {
+            self.last_damage = None;
+            self.force_full_rebuild = false;
+            self.dirty_bounds = None;
+            return;
+        }
+
+        self.last_damage = Some(self.dirty_bounds.unwrap_or((0, 0, 0, 0)));
+        self.dirty_bounds = None;
+    }
+
+    fn gl_draw(&mut self, _font : &Font, _shader : &Shader) { }
+    fn get_damage_bounds(&self) -> Option<(i32, i32, i32, i32)> { self.last_damage }
+
+    fn resize_pixels(&mut self, width_pixels : u32, height_pixels : u32) {
+        self.width_pixels = width_pixels;
+        self.height_pixels = height_pixels;
+        self.force_full_rebuild = true;
+    }
+
+    fn gl_draw_proportional(&mut self, glyph_cache : &mut GlyphCache, shader : &Shader) {
+        let mut vertices : Vec<f32> = Vec::new();
+
+        for (x, y, text, fg) in self.runs.iter() {
+            let mut pen_x = *x as f32;
+            let pen_y = *y as f32;
+
+            for c in text.chars() {
+                let glyph = glyph_cache.glyph(c, self.px_size);
+                let (gx0, gy0) = (pen_x + glyph.bearing.0, pen_y + glyph.bearing.1);
+                let (gx1, gy1) = (gx0 + glyph.size.0, gy0 + glyph.size.1);
+                let (u0, v0) = glyph.uv_min;
+                let (u1, v1) = glyph.uv_max;
+
+                vertices.extend_from_slice(&[
+                    gx0, gy0, u0, v0, fg.r, fg.g, fg.b,
+                    gx0, gy1, u0, v1, fg.r, fg.g, fg.b,
+                    gx1, gy1, u1, v1, fg.r, fg.g, fg.b,
+                    gx0, gy0, u0, v0, fg.r, fg.g, fg.b,
+                    gx1, gy1, u1, v1, fg.r, fg.g, fg.b,
+                    gx1, gy0, u1, v0, fg.r, fg.g, fg.b
+                ]);
+
+                pen_x += glyph.advance;
+            }
+        }
+
+        unsafe {
+            gl::BindVertexArray(self.vao);
+            gl::BindBuffer(gl::ARRAY_BUFFER, self.vbo);
+            gl::BufferData(gl::ARRAY_BUFFER, (vertices.len() * std::mem::size_of::<f32>()) as isize,
+                vertices.as_ptr() as *const _, gl::DYNAMIC_DRAW);
+
+            shader.useProgram();
+            gl::BindTexture(gl::TEXTURE_2D, glyph_cache.texture_id);
+            gl::DrawArrays(gl::TRIANGLES, 0, (vertices.len() / 7) as i32);
+        }
+    }
+}
+
+#[cfg(test)]
+mod cols_rows_for_pixels_tests {
+    use super::cols_rows_for_pixels;
+
+    #[test]
+    fn divides_pixels_by_tile_size() {
+        assert_eq!(cols_rows_for_pixels(640, 480, (8, 16)), (80, 30));
+    }
+
+    #[test]
+    fn rounds_down_when_it_does_not_divide_evenly() {
+        assert_eq!(cols_rows_for_pixels(645, 490, (8, 16)), (80, 30));
+    }
+
+    #[test]
+    fn always_keeps_at_least_one_column_and_row() {
+        assert_eq!(cols_rows_for_pixels(4, 4, (8, 16)), (1, 1));
+    }
+}
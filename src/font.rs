@@ -0,0 +1,44 @@
+extern crate gl;
+use std::path::Path;
+
+// A pre-baked bitmap font sheet (e.g. a CP437 tileset), uploaded to the GPU as a single
+// texture. `tile_size` is the pixel dimensions of one glyph cell and is what consoles use to
+// work out how many columns/rows fit in a given pixel area.
+pub struct Font {
+    pub filename : String,
+    pub tile_size : (u32, u32),
+    pub gl_id : Option<gl::types::GLuint>
+}
+
+impl Font {
+    pub fn new<S: ToString>(filename : S, tile_width : u32, tile_height : u32) -> Font {
+        Font {
+            filename: filename.to_string(),
+            tile_size: (tile_width, tile_height),
+            gl_id: None
+        }
+    }
+
+    pub fn setup_gl_texture(&mut self) {
+        let img = image::open(Path::new(&self.filename)).expect("Unable to load font image").to_rgba();
+        let (width, height) = img.dimensions();
+
+        unsafe {
+            let mut texture_id : gl::types::GLuint = 0;
+            gl::GenTextures(1, &mut texture_id);
+            gl::BindTexture(gl::TEXTURE_2D, texture_id);
+            gl::TexParameteri(gl::TEXTURE_2D, gl::TEXTURE_WRAP_S, gl::CLAMP_TO_EDGE as i32);
+            gl::TexParameteri(gl::TEXTURE_2D, gl::TEXTURE_WRAP_T, gl::CLAMP_TO_EDGE as i32);
+            gl::TexParameteri(gl::TEXTURE_2D, gl::TEXTURE_MIN_FILTER, gl::NEAREST as i32);
+            gl::TexParameteri(gl::TEXTURE_2D, gl::TEXTURE_MAG_FILTER, gl::NEAREST as i32);
+            gl::TexImage2D(gl::TEXTURE_2D, 0, gl::RGBA as i32, width as i32, height as i32,
+                0, gl::RGBA, gl::UNSIGNED_BYTE, img.into_raw().as_ptr() as *const _);
+
+            self.gl_id = Some(texture_id);
+        }
+    }
+
+    pub fn bind_texture(&self) {
+        unsafe { gl::BindTexture(gl::TEXTURE_2D, self.gl_id.expect("Font texture not set up yet")); }
+    }
+}
@@ -0,0 +1,177 @@
+extern crate gl;
+extern crate rusttype;
+use self::rusttype::{Font as TtfFont, Scale, point};
+use std::collections::HashMap;
+
+// UV rectangle plus layout metrics for one rasterized glyph, in texture and pixel space
+// respectively.
+#[derive(Clone, Copy)]
+pub struct GlyphInfo {
+    pub uv_min : (f32, f32),
+    pub uv_max : (f32, f32),
+    pub bearing : (f32, f32),
+    pub advance : f32,
+    pub size : (f32, f32)
+}
+
+// Pure shelf-packing bookkeeping for `GlyphCache`'s texture atlas, kept free of any GL calls
+// so the allocation logic can be exercised without a live GL context. Glyphs are placed
+// left-to-right on the current shelf; a new shelf starts under the tallest glyph on the
+// current shelf once a row runs out of width, and the atlas height doubles (resetting allocation) once a
+// shelf wouldn't fit vertically either.
+struct ShelfAllocator {
+    atlas_size : (u32, u32),
+    shelf_x : u32,
+    shelf_y : u32,
+    shelf_height : u32
+}
+
+impl ShelfAllocator {
+    fn new(atlas_size : (u32, u32)) -> ShelfAllocator {
+        ShelfAllocator { atlas_size, shelf_x: 0, shelf_y: 0, shelf_height: 0 }
+    }
+
+    // Reserves space for a `w`x`h` glyph, returning its top-left position and whether the
+    // atlas had to grow (and so its old contents/cache are no longer valid) to fit it.
+    fn allocate(&mut self, w : u32, h : u32) -> ((u32, u32), bool) {
+        if self.shelf_x + w > self.atlas_size.0 {
+            self.shelf_x = 0;
+            self.shelf_y += self.shelf_height;
+            self.shelf_height = 0;
+        }
+
+        let mut grew = false;
+        if self.shelf_y + h > self.atlas_size.1 {
+            self.atlas_size.1 *= 2;
+            self.shelf_x = 0;
+            self.shelf_y = 0;
+            self.shelf_height = 0;
+            grew = true;
+        }
+
+        let pos = (self.shelf_x, self.shelf_y);
+        self.shelf_x += w;
+        self.shelf_height = self.shelf_height.max(h);
+        (pos, grew)
+    }
+}
+
+// Rasterizes TTF/OTF glyphs on demand and packs them into a growable texture atlas, so
+// consoles can render proportional text instead of only a fixed CP437 grid.
+pub struct GlyphCache {
+    pub texture_id : gl::types::GLuint,
+    shelves : ShelfAllocator,
+    glyphs : HashMap<(char, u32), GlyphInfo>,
+    ttf : TtfFont<'static>
+}
+
+impl GlyphCache {
+    pub fn new(ttf_bytes : Vec<u8>, atlas_size : (u32, u32)) -> GlyphCache {
+        let ttf = TtfFont::try_from_vec(ttf_bytes).expect("Unable to parse TTF/OTF font data");
+        let mut texture_id : gl::types::GLuint = 0;
+        unsafe {
+            gl::GenTextures(1, &mut texture_id);
+            gl::BindTexture(gl::TEXTURE_2D, texture_id);
+            gl::TexImage2D(gl::TEXTURE_2D, 0, gl::RED as i32, atlas_size.0 as i32, atlas_size.1 as i32,
+                0, gl::RED, gl::UNSIGNED_BYTE, std::ptr::null());
+            gl::TexParameteri(gl::TEXTURE_2D, gl::TEXTURE_MIN_FILTER, gl::LINEAR as i32);
+            gl::TexParameteri(gl::TEXTURE_2D, gl::TEXTURE_MAG_FILTER, gl::LINEAR as i32);
+        }
+
+        GlyphCache {
+            texture_id,
+            shelves: ShelfAllocator::new(atlas_size),
+            glyphs: HashMap::new(),
+            ttf
+        }
+    }
+
+    // Looks up (or rasterizes and caches) the UV rectangle and metrics for a glyph at a
+    // given pixel size.
+    pub fn glyph(&mut self, c : char, px_size : u32) -> GlyphInfo {
+        if let Some(info) = self.glyphs.get(&(c, px_size)) {
+            return *info;
+        }
+
+        let scale = Scale::uniform(px_size as f32);
+        let glyph = self.ttf.glyph(c).scaled(scale).positioned(point(0.0, 0.0));
+        let bb = glyph.pixel_bounding_box().unwrap_or(rusttype::Rect { min: point(0, 0), max: point(0, 0) });
+        let glyph_w = (bb.max.x - bb.min.x).max(0) as u32;
+        let glyph_h = (bb.max.y - bb.min.y).max(0) as u32;
+
+        let ((x, y), grew) = self.shelves.allocate(glyph_w, glyph_h);
+        if grew {
+            // The atlas was reallocated at a new size: previously cached glyphs no longer
+            // have valid slots, so re-upload a blank texture and drop them.
+            self.glyphs.clear();
+            unsafe {
+                gl::BindTexture(gl::TEXTURE_2D, self.texture_id);
+                gl::TexImage2D(gl::TEXTURE_2D, 0, gl::RED as i32, self.shelves.atlas_size.0 as i32, self.shelves.atlas_size.1 as i32,
+                    0, gl::RED, gl::UNSIGNED_BYTE, std::ptr::null());
+            }
+        }
+
+        let mut coverage = vec![0u8; (glyph_w * glyph_h) as usize];
+        glyph.draw(|gx, gy, v| {
+            let idx = (gy * glyph_w + gx) as usize;
+            coverage[idx] = (v * 255.0) as u8;
+        });
+
+        unsafe {
+            gl::BindTexture(gl::TEXTURE_2D, self.texture_id);
+            gl::TexSubImage2D(gl::TEXTURE_2D, 0, x as i32, y as i32,
+                glyph_w as i32, glyph_h as i32, gl::RED, gl::UNSIGNED_BYTE, coverage.as_ptr() as *const _);
+        }
+
+        let (atlas_w, atlas_h) = (self.shelves.atlas_size.0 as f32, self.shelves.atlas_size.1 as f32);
+        let info = GlyphInfo {
+            uv_min: (x as f32 / atlas_w, y as f32 / atlas_h),
+            uv_max: ((x + glyph_w) as f32 / atlas_w, (y + glyph_h) as f32 / atlas_h),
+            bearing: (bb.min.x as f32, bb.min.y as f32),
+            advance: glyph.unpositioned().h_metrics().advance_width,
+            size: (glyph_w as f32, glyph_h as f32)
+        };
+
+        self.glyphs.insert((c, px_size), info);
+        info
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ShelfAllocator;
+
+    #[test]
+    fn packs_glyphs_left_to_right_on_one_shelf() {
+        let mut shelves = ShelfAllocator::new((100, 100));
+        let (pos1, grew1) = shelves.allocate(10, 20);
+        let (pos2, grew2) = shelves.allocate(15, 12);
+
+        assert_eq!(pos1, (0, 0));
+        assert_eq!(pos2, (10, 0));
+        assert!(!grew1 && !grew2);
+    }
+
+    #[test]
+    fn starts_a_new_shelf_once_a_row_is_full() {
+        let mut shelves = ShelfAllocator::new((20, 100));
+        let (pos1, _) = shelves.allocate(15, 10);
+        let (pos2, grew) = shelves.allocate(10, 5);
+
+        assert_eq!(pos1, (0, 0));
+        assert_eq!(pos2, (0, 10));
+        assert!(!grew);
+    }
+
+    #[test]
+    fn grows_the_atlas_once_a_shelf_would_not_fit_vertically() {
+        let mut shelves = ShelfAllocator::new((20, 10));
+        let (_, grew1) = shelves.allocate(20, 8);
+        let (pos2, grew2) = shelves.allocate(5, 5);
+
+        assert!(!grew1);
+        assert!(grew2);
+        assert_eq!(shelves.atlas_size, (20, 20));
+        assert_eq!(pos2, (0, 0));
+    }
+}
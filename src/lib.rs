@@ -0,0 +1,22 @@
+pub mod rltk;
+pub mod console;
+pub mod font;
+pub mod shader;
+pub mod color;
+pub mod glyph_cache;
+
+pub use rltk::Rltk;
+pub use console::{Console, SimpleConsole, TextConsole};
+pub use font::Font;
+pub use shader::Shader;
+pub use color::RGB;
+pub use glyph_cache::{GlyphCache, GlyphInfo};
+
+// Implemented by applications to receive per-frame callbacks from `Rltk::main_loop`.
+pub trait GameState {
+    fn tick(&mut self, ctx : &mut Rltk);
+
+    // Called once a framebuffer resize has been propagated (new dimensions already applied
+    // to `ctx`), so applications can relayout their UI. No-op by default.
+    fn on_resize(&mut self, _width : u32, _height : u32) { }
+}
@@ -1,18 +1,166 @@
 extern crate glfw;
 use self::glfw::{Context, Action};
 extern crate gl;
+use std::collections::HashSet;
+use std::collections::VecDeque;
 use std::sync::mpsc::Receiver;
 use super::GameState;
-use std::time::{Instant};
+use std::time::{Instant, Duration};
+use std::thread::sleep;
 use super::font;
+use super::glyph_cache::GlyphCache;
 use super::Console;
 use super::Shader;
 use super::RGB;
 
+// Virtual key codes handed to GameState::tick. For now this is a thin alias over glfw's own
+// key enum - if we ever support other back-ends this is the seam where a real translation
+// table would live.
+pub type VirtualKeyCode = glfw::Key;
+
+// Keeps a ring buffer of the last N frame durations so applications (and the crate itself)
+// can read a stable average/percentile frame time instead of just the latest sample.
+pub struct FrameTimeMeter {
+    samples : VecDeque<f32>,
+    capacity : usize
+}
+
+impl FrameTimeMeter {
+    pub fn new(capacity : usize) -> FrameTimeMeter {
+        FrameTimeMeter { samples: VecDeque::with_capacity(capacity), capacity }
+    }
+
+    pub fn push(&mut self, frame_time_ms : f32) {
+        if self.samples.len() == self.capacity {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(frame_time_ms);
+    }
+
+    pub fn average_ms(&self) -> f32 {
+        if self.samples.is_empty() { return 0.0; }
+        self.samples.iter().sum::<f32>() / self.samples.len() as f32
+    }
+
+    // Nearest-rank percentile, e.g. `percentile_ms(0.99)` for p99 frame time.
+    pub fn percentile_ms(&self, percentile : f32) -> f32 {
+        if self.samples.is_empty() { return 0.0; }
+        let mut sorted : Vec<f32> = self.samples.iter().cloned().collect();
+        sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        let idx = ((sorted.len() - 1) as f32 * percentile).round() as usize;
+        sorted[idx]
+    }
+}
+
+#[cfg(test)]
+mod frame_time_meter_tests {
+    use super::FrameTimeMeter;
+
+    #[test]
+    fn empty_meter_reports_zero() {
+        let meter = FrameTimeMeter::new(4);
+        assert_eq!(meter.average_ms(), 0.0);
+        assert_eq!(meter.percentile_ms(0.99), 0.0);
+    }
+
+    #[test]
+    fn averages_the_samples_it_has_seen() {
+        let mut meter = FrameTimeMeter::new(4);
+        meter.push(10.0);
+        meter.push(20.0);
+        assert_eq!(meter.average_ms(), 15.0);
+    }
+
+    #[test]
+    fn drops_the_oldest_sample_once_the_ring_buffer_is_full() {
+        let mut meter = FrameTimeMeter::new(2);
+        meter.push(10.0);
+        meter.push(20.0);
+        meter.push(30.0);
+
+        // The 10.0 sample should have been evicted, leaving only 20.0 and 30.0.
+        assert_eq!(meter.average_ms(), 25.0);
+    }
+
+    #[test]
+    fn percentile_is_nearest_rank_over_sorted_samples() {
+        let mut meter = FrameTimeMeter::new(5);
+        for ms in [5.0, 1.0, 4.0, 2.0, 3.0] {
+            meter.push(ms);
+        }
+
+        assert_eq!(meter.percentile_ms(0.0), 1.0);
+        assert_eq!(meter.percentile_ms(1.0), 5.0);
+    }
+}
+
+// Pure pixel-to-cell conversion behind `Rltk::pixels_to_cell`, kept free of `self` so the
+// missing-console/degenerate-tile-size fallback can be exercised without a live GL context.
+fn cell_for_pixel(x : f64, y : f64, tile_size : Option<(u32, u32)>) -> (i32, i32) {
+    match tile_size {
+        Some((tw, th)) if tw > 0 && th > 0 => ((x as i32) / tw as i32, (y as i32) / th as i32),
+        _ => (x as i32, y as i32)
+    }
+}
+
+#[cfg(test)]
+mod cell_for_pixel_tests {
+    use super::cell_for_pixel;
+
+    #[test]
+    fn divides_pixels_by_tile_size() {
+        assert_eq!(cell_for_pixel(100.0, 64.0, Some((32, 16))), (3, 4));
+    }
+
+    #[test]
+    fn falls_back_to_raw_pixels_with_no_tile_size() {
+        assert_eq!(cell_for_pixel(100.0, 64.0, None), (100, 64));
+    }
+
+    #[test]
+    fn falls_back_to_raw_pixels_with_a_degenerate_tile_size() {
+        assert_eq!(cell_for_pixel(100.0, 64.0, Some((0, 16))), (100, 64));
+        assert_eq!(cell_for_pixel(100.0, 64.0, Some((32, 0))), (100, 64));
+    }
+}
+
+// Shared bookkeeping behind every `register_*` method: push a new handle-indexed resource and
+// hand back the index it now lives at. Kept generic and free of `self` so the "index returned
+// is always len-1 after a push" invariant can be tested without building a Shader/Font/GlyphCache.
+fn push_handle<T>(items : &mut Vec<T>, item : T) -> usize {
+    items.push(item);
+    items.len() - 1
+}
+
+#[cfg(test)]
+mod push_handle_tests {
+    use super::push_handle;
+
+    #[test]
+    fn first_push_gets_index_zero() {
+        let mut items : Vec<&str> = Vec::new();
+        assert_eq!(push_handle(&mut items, "a"), 0);
+    }
+
+    #[test]
+    fn successive_pushes_get_sequential_indices() {
+        let mut items : Vec<&str> = Vec::new();
+        push_handle(&mut items, "a");
+        push_handle(&mut items, "b");
+        assert_eq!(push_handle(&mut items, "c"), 2);
+    }
+}
+
 pub struct DisplayConsole {
     pub console : Box<Console>,
     pub shader_index : usize,
-    pub font_index : usize
+    pub font_index : usize,
+    // Set for a console that should also render a proportional text layer; indexes
+    // `Rltk::glyph_caches`.
+    pub glyph_cache_index : Option<usize>,
+    // Shader used for the proportional text layer above, if any. Proportional text has its
+    // own pos/uv/fg vertex layout (no bg), so it can't share `shader_index`'s grid shader.
+    pub text_shader_index : Option<usize>
 }
 
 #[allow(non_snake_case)]
@@ -23,11 +171,40 @@ pub struct Rltk {
     pub width_pixels : u32,
     pub height_pixels : u32,
     pub fonts : Vec<font::Font>,
+    pub glyph_caches : Vec<GlyphCache>,
     pub shaders : Vec<Shader>,
     pub consoles : Vec<DisplayConsole>,
     pub fps : f32,
     pub frame_time_ms : f32,
-    pub active_console : usize
+    pub active_console : usize,
+
+    // Input state, refreshed every frame before GameState::tick is called.
+    pub keys_down : HashSet<VirtualKeyCode>,
+    pub key : Option<VirtualKeyCode>,
+    pub mouse_pos : (i32, i32),
+    pub left_click : bool,
+
+    // Frame pacing: `vsync` starts out true, but the swap interval is only set when
+    // `set_vsync` is called (window creation leaves glfw's default), and an optional FPS cap
+    // sleeps out the remainder of the frame budget after swap_buffers.
+    pub vsync : bool,
+    pub target_fps : Option<f32>,
+    pub frame_meter : FrameTimeMeter,
+
+    // Full-screen post-process pass: when set, every console is rendered into an offscreen
+    // FBO first, and that FBO's texture is drawn to the screen through this shader.
+    pub post_process_shader : Option<usize>,
+    post_process_target : Option<PostProcessTarget>
+}
+
+// Offscreen render target and fullscreen quad used to apply a post-process shader to the
+// fully-composited frame before it hits the default framebuffer.
+struct PostProcessTarget {
+    fbo : gl::types::GLuint,
+    texture : gl::types::GLuint,
+    quad_vao : gl::types::GLuint,
+    quad_vbo : gl::types::GLuint,
+    size : (u32, u32)
 }
 
 #[allow(dead_code)]
@@ -65,24 +242,184 @@ impl Rltk {
             width_pixels : width_pixels,
             height_pixels: height_pixels,
             fonts : Vec::new(),
+            glyph_caches: Vec::new(),
             consoles: Vec::new(),
             shaders: vec![vs],
             fps: 0.0,
             frame_time_ms: 0.0,
-            active_console : 0
+            active_console : 0,
+            keys_down: HashSet::new(),
+            key: None,
+            mouse_pos: (0, 0),
+            left_click: false,
+            vsync: true,
+            target_fps: None,
+            frame_meter: FrameTimeMeter::new(120),
+            post_process_shader: None,
+            post_process_target: None
+        };
+    }
+
+    // Registers an additional shader (for per-console use or as a post-process pass) and
+    // returns its handle number.
+    pub fn register_shader(&mut self, vertex_path : &str, fragment_path : &str) -> usize {
+        push_handle(&mut self.shaders, Shader::new(vertex_path, fragment_path))
+    }
+
+    // Assigns a registered shader to a registered console, replacing its default one.
+    pub fn set_console_shader(&mut self, console_id : usize, shader_id : usize) {
+        self.consoles[console_id].shader_index = shader_id;
+    }
+
+    // Sets (or clears) the full-screen post-process shader. When set, all consoles are
+    // rendered into an offscreen framebuffer and composited to the screen through this
+    // shader as a fullscreen quad instead of drawing straight to the default framebuffer.
+    pub fn set_post_process(&mut self, shader_id : Option<usize>) {
+        self.post_process_shader = shader_id;
+    }
+
+    // Lazily creates (or resizes) the offscreen FBO/texture and fullscreen quad used for
+    // the post-process pass.
+    fn ensure_post_process_target(&mut self) -> &PostProcessTarget {
+        let needs_rebuild = match &self.post_process_target {
+            None => true,
+            Some(target) => target.size != (self.width_pixels, self.height_pixels)
         };
+
+        if needs_rebuild {
+            // A resize replaces the whole target below; free the GL objects it's replacing
+            // first so repeated resizes don't leak a framebuffer/texture/VAO/VBO per resize.
+            if let Some(old) = self.post_process_target.take() {
+                unsafe {
+                    gl::DeleteFramebuffers(1, &old.fbo);
+                    gl::DeleteTextures(1, &old.texture);
+                    gl::DeleteVertexArrays(1, &old.quad_vao);
+                    gl::DeleteBuffers(1, &old.quad_vbo);
+                }
+            }
+
+            unsafe {
+                let mut fbo = 0;
+                let mut texture = 0;
+                gl::GenFramebuffers(1, &mut fbo);
+                gl::GenTextures(1, &mut texture);
+                gl::BindTexture(gl::TEXTURE_2D, texture);
+                gl::TexImage2D(gl::TEXTURE_2D, 0, gl::RGBA as i32, self.width_pixels as i32, self.height_pixels as i32,
+                    0, gl::RGBA, gl::UNSIGNED_BYTE, std::ptr::null());
+                gl::TexParameteri(gl::TEXTURE_2D, gl::TEXTURE_MIN_FILTER, gl::LINEAR as i32);
+                gl::TexParameteri(gl::TEXTURE_2D, gl::TEXTURE_MAG_FILTER, gl::LINEAR as i32);
+
+                gl::BindFramebuffer(gl::FRAMEBUFFER, fbo);
+                gl::FramebufferTexture2D(gl::FRAMEBUFFER, gl::COLOR_ATTACHMENT0, gl::TEXTURE_2D, texture, 0);
+
+                // Two triangles covering clip space, carrying position and UV.
+                let quad : [f32; 24] = [
+                    -1.0,  1.0,  0.0, 1.0,
+                    -1.0, -1.0,  0.0, 0.0,
+                     1.0, -1.0,  1.0, 0.0,
+                    -1.0,  1.0,  0.0, 1.0,
+                     1.0, -1.0,  1.0, 0.0,
+                     1.0,  1.0,  1.0, 1.0
+                ];
+                let mut vao = 0;
+                let mut vbo = 0;
+                gl::GenVertexArrays(1, &mut vao);
+                gl::GenBuffers(1, &mut vbo);
+                gl::BindVertexArray(vao);
+                gl::BindBuffer(gl::ARRAY_BUFFER, vbo);
+                gl::BufferData(gl::ARRAY_BUFFER, (quad.len() * std::mem::size_of::<f32>()) as isize,
+                    quad.as_ptr() as *const _, gl::STATIC_DRAW);
+                gl::VertexAttribPointer(0, 2, gl::FLOAT, gl::FALSE, 4 * std::mem::size_of::<f32>() as i32, std::ptr::null());
+                gl::EnableVertexAttribArray(0);
+                gl::VertexAttribPointer(1, 2, gl::FLOAT, gl::FALSE, 4 * std::mem::size_of::<f32>() as i32,
+                    (2 * std::mem::size_of::<f32>()) as *const _);
+                gl::EnableVertexAttribArray(1);
+
+                gl::BindFramebuffer(gl::FRAMEBUFFER, 0);
+
+                self.post_process_target = Some(PostProcessTarget {
+                    fbo, texture, quad_vao: vao, quad_vbo: vbo, size: (self.width_pixels, self.height_pixels)
+                });
+            }
+        }
+
+        self.post_process_target.as_ref().unwrap()
+    }
+
+    // Enables or disables waiting for the monitor's refresh (glfw's swap interval). Systems
+    // without a working vsync should pair this with `set_target_fps` as a software fallback.
+    pub fn set_vsync(&mut self, enabled : bool) {
+        self.vsync = enabled;
+        self.glfw.set_swap_interval(if enabled { glfw::SwapInterval::Sync(1) } else { glfw::SwapInterval::None });
+    }
+
+    // Caps the main loop to roughly this many frames per second by sleeping out whatever's
+    // left of the frame budget after `swap_buffers`. Pass `None` to remove the cap.
+    pub fn set_target_fps(&mut self, fps : Option<f32>) {
+        self.target_fps = fps;
+    }
+
+    // Converts a pixel-space cursor position into the active console's character cell,
+    // using that console's font to figure out the size of a tile.
+    fn pixels_to_cell(&self, x : f64, y : f64) -> (i32, i32) {
+        // CursorPos events can arrive before any console/font has been registered (or in
+        // principle with a font that reports a degenerate tile size); fall back to raw
+        // pixels rather than panicking on an out-of-bounds index or a divide by zero.
+        let tile_size = self.consoles.get(self.active_console)
+            .and_then(|cons| self.fonts.get(cons.font_index))
+            .map(|font| font.tile_size);
+
+        cell_for_pixel(x, y, tile_size)
+    }
+
+    // Forces every console to re-derive its column/row count from its font's glyph
+    // dimensions, reallocating its backing tile buffer and marking itself fully dirty.
+    // `Console::resize_pixels` takes the new pixel dimensions (not cols/rows) and does the
+    // tile-size division itself - it's the one that knows its own font's tile size.
+    fn resize_consoles(&mut self, width : u32, height : u32) {
+        self.width_pixels = width;
+        self.height_pixels = height;
+
+        for cons in self.consoles.iter_mut() {
+            cons.console.resize_pixels(width, height);
+        }
     }
 
     // Message pump handler for RLTK applications
-    fn process_events(&mut self) {
+    fn process_events(&mut self, gamestate: &mut GameState) {
         for (_, event) in glfw::flush_messages(&self.events) {
 
             match event {
                 glfw::WindowEvent::FramebufferSize(width, height) => {
                     // make sure the viewport matches the new window dimensions; note that width and
-                    // height will be significantly larger than specified on retina displays.
-                    unsafe { gl::Viewport(0, 0, width, height) }
-                }                    
+                    // height will be significantly larger than specified on retina displays (and
+                    // glfw is known to re-fire this with identical dimensions, so bail out early).
+                    let (width, height) = (width as u32, height as u32);
+                    if width == self.width_pixels && height == self.height_pixels {
+                        continue;
+                    }
+
+                    unsafe { gl::Viewport(0, 0, width as i32, height as i32) }
+                    self.resize_consoles(width, height);
+                    gamestate.on_resize(width, height);
+                }
+                glfw::WindowEvent::Key(key, _, action, _) => {
+                    match action {
+                        Action::Press | Action::Repeat => {
+                            self.keys_down.insert(key);
+                            self.key = Some(key);
+                        }
+                        Action::Release => {
+                            self.keys_down.remove(&key);
+                        }
+                    }
+                }
+                glfw::WindowEvent::CursorPos(x, y) => {
+                    self.mouse_pos = self.pixels_to_cell(x, y);
+                }
+                glfw::WindowEvent::MouseButton(glfw::MouseButton::Button1, Action::Press, _) => {
+                    self.left_click = true;
+                }
                 _ => { }
             }
         }
@@ -96,6 +433,7 @@ impl Rltk {
         let mut frames = 0;
 
         while !self.window.should_close() {
+            let frame_start = Instant::now();
             let now_seconds = now.elapsed().as_secs();
             frames += 1;
 
@@ -113,31 +451,101 @@ impl Rltk {
 
             // events
             // -----
-            self.process_events();
+            // One-shot input fields are edge-triggered: reset them before polling so a
+            // consumer only ever sees this frame's press/click, not a stale one.
+            self.key = None;
+            self.left_click = false;
+            self.process_events(gamestate);
             gamestate.tick(self);
 
             // Console structure - doesn't really have to be every frame...
+            // rebuild_if_dirty now patches only the damaged cell ranges via glBufferSubData
+            // (a full cls/resize still forces a complete rebuild internally).
             for cons in self.consoles.iter_mut() {
                 cons.console.rebuild_if_dirty();
             }
 
-            // Clear the screen
+            // If a post-process shader is active, render every console into an offscreen
+            // FBO instead of straight to the screen; we composite it through that shader below.
+            let post_process_fbo = self.post_process_shader.map(|_| self.ensure_post_process_target().fbo);
             unsafe {
+                gl::BindFramebuffer(gl::FRAMEBUFFER, post_process_fbo.unwrap_or(0));
                 gl::ClearColor(0.2, 0.3, 0.3, 1.0);
-                gl::Clear(gl::COLOR_BUFFER_BIT);
             }
-            
-            // Tell each console to draw itself
+
+            // Tell each console to draw itself. A console with no reported damage bounds needs
+            // a genuine full redraw (first frame, `cls`, or a resize); one that reports bounds
+            // only needs its scissored region cleared and redrawn. But an unscissored clear
+            // wipes the *whole* framebuffer, including whatever an earlier console in this
+            // same frame already drew into its own scissored region - so whether any console
+            // needs a full redraw has to be decided across all of them before anything is
+            // cleared, not console-by-console as we go.
+            let needs_full_redraw = self.consoles.iter()
+                .any(|cons| cons.console.get_damage_bounds().is_none());
+
+            if needs_full_redraw {
+                unsafe { gl::Clear(gl::COLOR_BUFFER_BIT); }
+            }
+
             for cons in self.consoles.iter_mut() {
                 let font = &self.fonts[cons.font_index];
                 let shader = &self.shaders[cons.shader_index];
-                cons.console.gl_draw(font, shader);
-            } 
+
+                if needs_full_redraw {
+                    cons.console.gl_draw(font, shader);
+                } else if let Some((x, y, w, h)) = cons.console.get_damage_bounds() {
+                    if (w, h) != (0, 0) {
+                        unsafe {
+                            gl::Enable(gl::SCISSOR_TEST);
+                            gl::Scissor(x, y, w, h);
+                            gl::Clear(gl::COLOR_BUFFER_BIT);
+                            cons.console.gl_draw(font, shader);
+                            gl::Disable(gl::SCISSOR_TEST);
+                        }
+                    }
+                }
+
+                // Consoles registered via `register_console_proportional` also draw a
+                // proportional text layer through their assigned glyph cache, using their own
+                // text shader rather than the grid shader above (incompatible vertex layouts).
+                if let Some(glyph_cache_index) = cons.glyph_cache_index {
+                    let glyph_cache = &mut self.glyph_caches[glyph_cache_index];
+                    let text_shader = &self.shaders[cons.text_shader_index.expect("proportional console missing a text shader")];
+                    cons.console.gl_draw_proportional(glyph_cache, text_shader);
+                }
+            }
+
+            // Composite the offscreen frame to the screen through the post-process shader,
+            // drawn as a single fullscreen quad.
+            if let Some(shader_id) = self.post_process_shader {
+                let target = self.ensure_post_process_target();
+                let (quad_vao, texture) = (target.quad_vao, target.texture);
+                unsafe {
+                    gl::BindFramebuffer(gl::FRAMEBUFFER, 0);
+                    gl::Clear(gl::COLOR_BUFFER_BIT);
+                    self.shaders[shader_id].useProgram();
+                    gl::BindTexture(gl::TEXTURE_2D, texture);
+                    gl::BindVertexArray(quad_vao);
+                    gl::DrawArrays(gl::TRIANGLES, 0, 6);
+                }
+            }
 
             // glfw: swap buffers and poll IO events (keys pressed/released, mouse moved etc.)
             // -------------------------------------------------------------------------------
             self.window.swap_buffers();
             self.glfw.poll_events();
+
+            // Soft frame cap: if we're under budget for the target FPS, sleep out the
+            // remainder, whether or not vsync is enabled. This also lets systems
+            // with a broken/absent vsync start up with a sane frame rate regardless.
+            let elapsed_ms = frame_start.elapsed().as_millis() as f32;
+            if let Some(target_fps) = self.target_fps {
+                let budget_ms = 1000.0 / target_fps;
+                if elapsed_ms < budget_ms {
+                    sleep(Duration::from_millis((budget_ms - elapsed_ms) as u64));
+                }
+            }
+            self.frame_meter.push(frame_start.elapsed().as_millis() as f32);
         }
     }
 
@@ -145,14 +553,33 @@ impl Rltk {
     pub fn register_font(&mut self, mut font : font::Font) -> usize {
         font.setup_gl_texture();
         font.bind_texture();
-        self.fonts.push(font);
-        self.fonts.len()-1
+        push_handle(&mut self.fonts, font)
+    }
+
+    // Registers a scalable TTF/OTF font backed by a `GlyphCache` rather than a pre-baked
+    // bitmap sheet, and returns its handle number. Pair this with `register_console_proportional`
+    // so a console actually draws through it - `main_loop` looks glyphs up by character for any
+    // console that has a glyph cache assigned, instead of assuming a uniform grid. The actual
+    // rasterization size is whatever `px_size` the console it's paired with was built with -
+    // `GlyphCache::glyph` caches per-size anyway, so nothing here needs to pin one down.
+    pub fn register_ttf_font<S: ToString>(&mut self, path : S) -> usize {
+        let bytes = std::fs::read(path.to_string()).expect("Unable to read font file");
+        push_handle(&mut self.glyph_caches, GlyphCache::new(bytes, (1024, 1024)))
     }
 
     // Registers a new console terminal for output, and returns its handle number.
     pub fn register_console(&mut self, new_console : Box<Console>, font_index : usize) -> usize {
-        self.consoles.push(DisplayConsole{ console:new_console, font_index: font_index, shader_index: 0 });
-        self.consoles.len()-1
+        push_handle(&mut self.consoles, DisplayConsole{ console:new_console, font_index: font_index, shader_index: 0, glyph_cache_index: None, text_shader_index: None })
+    }
+
+    // Registers a console whose proportional text layer is drawn through a `GlyphCache`
+    // returned by `register_ttf_font`. That layer comes from `Console::gl_draw_proportional`,
+    // which only `TextConsole` implements (it draws no fixed bitmap grid); for a
+    // `SimpleConsole` the call is a no-op and only its grid is drawn.
+    // `text_shader_id` must point at a shader built for proportional text's pos/uv/fg vertex
+    // layout (no bg) - it can't reuse the console's own grid shader.
+    pub fn register_console_proportional(&mut self, new_console : Box<Console>, font_index : usize, glyph_cache_index : usize, text_shader_id : usize) -> usize {
+        push_handle(&mut self.consoles, DisplayConsole{ console:new_console, font_index: font_index, shader_index: 0, glyph_cache_index: Some(glyph_cache_index), text_shader_index: Some(text_shader_id) })
     }
 
     pub fn set_active_console(&mut self, id : usize) {
@@ -172,4 +599,6 @@ impl Console for Rltk {
     fn cls_bg(&mut self, background : RGB) { self.consoles[self.active_console].console.cls_bg(background); }
     fn print(&mut self, x:i32, y:i32, output:&str) { self.consoles[self.active_console].console.print(x, y, output); }
     fn print_color(&mut self, x:i32, y:i32, fg:RGB, bg:RGB, output:&str) { self.consoles[self.active_console].console.print_color(x,y,fg,bg,output); }
+    fn get_damage_bounds(&self) -> Option<(i32, i32, i32, i32)> { self.consoles[self.active_console].console.get_damage_bounds() }
+    fn resize_pixels(&mut self, width_pixels : u32, height_pixels : u32) { self.consoles[self.active_console].console.resize_pixels(width_pixels, height_pixels); }
 }
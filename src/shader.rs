@@ -0,0 +1,70 @@
+extern crate gl;
+use std::ffi::CString;
+use std::fs;
+use std::ptr;
+use std::str;
+
+// A linked GL shader program. Follows the LearnOpenGL compile/link/check-errors pattern the
+// rest of this crate is built around.
+pub struct Shader {
+    pub id : gl::types::GLuint
+}
+
+impl Shader {
+    pub fn new(vertex_path : &str, fragment_path : &str) -> Shader {
+        let vertex_src = fs::read_to_string(vertex_path).expect("Unable to read vertex shader");
+        let fragment_src = fs::read_to_string(fragment_path).expect("Unable to read fragment shader");
+
+        unsafe {
+            let vertex = Shader::compile(gl::VERTEX_SHADER, &vertex_src);
+            let fragment = Shader::compile(gl::FRAGMENT_SHADER, &fragment_src);
+
+            let id = gl::CreateProgram();
+            gl::AttachShader(id, vertex);
+            gl::AttachShader(id, fragment);
+            gl::LinkProgram(id);
+            Shader::check_link_errors(id);
+
+            gl::DeleteShader(vertex);
+            gl::DeleteShader(fragment);
+
+            Shader{ id }
+        }
+    }
+
+    unsafe fn compile(kind : gl::types::GLenum, source : &str) -> gl::types::GLuint {
+        let id = gl::CreateShader(kind);
+        let c_source = CString::new(source.as_bytes()).unwrap();
+        gl::ShaderSource(id, 1, &c_source.as_ptr(), ptr::null());
+        gl::CompileShader(id);
+
+        let mut success = gl::FALSE as gl::types::GLint;
+        gl::GetShaderiv(id, gl::COMPILE_STATUS, &mut success);
+        if success != gl::TRUE as gl::types::GLint {
+            let mut len = 0;
+            gl::GetShaderiv(id, gl::INFO_LOG_LENGTH, &mut len);
+            let mut buffer = vec![0u8; len as usize];
+            gl::GetShaderInfoLog(id, len, ptr::null_mut(), buffer.as_mut_ptr() as *mut gl::types::GLchar);
+            panic!("Shader compile error: {}", str::from_utf8(&buffer).unwrap());
+        }
+
+        id
+    }
+
+    unsafe fn check_link_errors(id : gl::types::GLuint) {
+        let mut success = gl::FALSE as gl::types::GLint;
+        gl::GetProgramiv(id, gl::LINK_STATUS, &mut success);
+        if success != gl::TRUE as gl::types::GLint {
+            let mut len = 0;
+            gl::GetProgramiv(id, gl::INFO_LOG_LENGTH, &mut len);
+            let mut buffer = vec![0u8; len as usize];
+            gl::GetProgramInfoLog(id, len, ptr::null_mut(), buffer.as_mut_ptr() as *mut gl::types::GLchar);
+            panic!("Shader link error: {}", str::from_utf8(&buffer).unwrap());
+        }
+    }
+
+    #[allow(non_snake_case)]
+    pub fn useProgram(&self) {
+        unsafe { gl::UseProgram(self.id); }
+    }
+}